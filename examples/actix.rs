@@ -1,38 +1,35 @@
 use actix_web::{get, http, post, web, App, HttpResponse, HttpServer, Responder};
-use link_rs::LinkGenerator;
+use link_rs::{is_expired, InMemoryStore, Link, LinkGenerator, LinkStore};
 use qrcode::{render::svg, types::QrError, QrCode};
 use serde::{Deserialize, Serialize};
-use std::{
-    collections::HashMap,
-    sync::{Arc, Mutex},
-};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 #[derive(Clone)]
 struct StateGaurd {
+    store: Arc<dyn LinkStore>,
     state: Arc<Mutex<State>>,
 }
 
 struct State {
-    data: HashMap<String, String>,
     generator: LinkGenerator,
 }
 
 impl StateGaurd {
     fn new() -> Self {
-        let state = Arc::new(Mutex::new(State {
-            data: HashMap::new(),
-            generator: LinkGenerator::new("/redirect", 10),
-        }));
-        StateGaurd { state }
+        let store: Arc<dyn LinkStore> = Arc::new(InMemoryStore::new());
+
+        let mut generator = LinkGenerator::new("/redirect", 10);
+        generator.set_store(store.clone());
+
+        StateGaurd {
+            store,
+            state: Arc::new(Mutex::new(State { generator })),
+        }
     }
 
-    pub fn get_url(&self, url: String) -> Option<String> {
-        self.state
-            .lock()
-            .unwrap()
-            .data
-            .get(&url)
-            .map(|u| u.to_owned())
+    pub fn get_url(&self, key: &str) -> Option<(String, Option<u64>)> {
+        self.store.get(key)
     }
 
     pub fn with_lock<F, T>(&self, func: F) -> T
@@ -47,6 +44,9 @@ impl StateGaurd {
 #[derive(Deserialize)]
 struct Request {
     url: String,
+    /// How long the generated link should stay valid for, in seconds.
+    #[serde(default)]
+    ttl_secs: Option<u64>,
 }
 
 #[derive(Serialize)]
@@ -57,10 +57,12 @@ struct Response {
 
 #[post("/generate")]
 async fn generate(req: web::Json<Request>, data: web::Data<StateGaurd>) -> impl Responder {
-    match data.with_lock(|state| -> Result<(QrCode, String), QrError> {
-        let (qr, link) = state.generator.generate_qr()?;
+    let store = data.store.clone();
+    let ttl = req.ttl_secs.map(Duration::from_secs);
+    match data.with_lock(move |state| -> Result<(QrCode, String), QrError> {
+        let (qr, link) = state.generator.generate_qr(ttl)?;
 
-        state.data.insert(link.key, (*req.url).to_string());
+        store.insert(&link.key, &req.url, link.expires_at);
         Ok((qr, link.url))
     }) {
         Ok((qr, url)) => {
@@ -80,10 +82,30 @@ async fn generate(req: web::Json<Request>, data: web::Data<StateGaurd>) -> impl
 async fn redirect(url: web::Path<String>, state: web::Data<StateGaurd>) -> impl Responder {
     println!("Redirect on: {url}");
 
-    match state.get_url(url.to_string()) {
-        Some(link) => HttpResponse::TemporaryRedirect()
-            .append_header(("location", link))
-            .finish(),
+    if !state.with_lock(|state| state.generator.is_valid_key(&url)) {
+        return HttpResponse::NotFound().finish();
+    }
+
+    match state.get_url(&url) {
+        Some((target, expires_at)) => {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs();
+            let link = Link {
+                key: url.to_string(),
+                url: target.clone(),
+                expires_at,
+            };
+
+            if is_expired(&link, now) {
+                HttpResponse::Gone().finish()
+            } else {
+                HttpResponse::TemporaryRedirect()
+                    .append_header(("location", target))
+                    .finish()
+            }
+        }
         None => HttpResponse::NotFound().finish(),
     }
 }
@@ -0,0 +1,152 @@
+//! Stateless, self-describing keys for [`LinkGenerator`](crate::LinkGenerator).
+//!
+//! A key embeds the target URL (and an optional expiry), AES-256-GCM encrypted under the
+//! generator's key: a freshly generated 12-byte nonce prepended to the ciphertext, base64url
+//! (no padding) encoded.
+
+use aes_gcm::aead::{rand_core::RngCore, Aead, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+
+use crate::{Link, LinkGenerator};
+
+const NONCE_LEN: usize = 12;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Payload {
+    target: String,
+    exp: Option<u64>,
+}
+
+/// The target URL and expiry recovered from a decoded stateless key.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Target {
+    pub target: String,
+    pub exp: Option<u64>,
+}
+
+/// Reasons decoding a stateless key can fail.
+#[derive(Debug, Error)]
+pub enum DecodeError {
+    #[error("key is not valid base64url")]
+    InvalidEncoding,
+    #[error("key is too short to contain a nonce")]
+    Truncated,
+    #[error("key failed to decrypt, it may be forged or use the wrong encryption key")]
+    DecryptionFailed,
+    #[error("decrypted payload could not be deserialized")]
+    Deserialization,
+    #[error("link has expired")]
+    Expired,
+}
+
+impl LinkGenerator {
+    /// Encode `target` (and an optional expiry, as a unix timestamp) into a stateless, encrypted
+    /// key and return the [`Link`] pointing at it.
+    ///
+    /// # Panics
+    /// Panics if this `LinkGenerator` was not constructed with [`LinkGenerator::new_encrypted`].
+    pub fn encode_target(&self, target: &str, expiry: Option<u64>) -> Link {
+        let key = self
+            .encryption_key
+            .expect("encode_target requires a LinkGenerator created with `new_encrypted`");
+
+        let payload = Payload {
+            target: target.to_string(),
+            exp: expiry,
+        };
+        let plaintext =
+            bincode::serialize(&payload).expect("Payload is always serializable");
+
+        let mut nonce_bytes = [0u8; NONCE_LEN];
+        OsRng.fill_bytes(&mut nonce_bytes);
+        let nonce = Nonce::from_slice(&nonce_bytes);
+
+        let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key));
+        let ciphertext = cipher
+            .encrypt(nonce, plaintext.as_slice())
+            .expect("encryption cannot fail with a freshly generated nonce");
+
+        let mut bytes = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+        bytes.extend_from_slice(&nonce_bytes);
+        bytes.extend_from_slice(&ciphertext);
+
+        Link::new(&self.redirect_url, URL_SAFE_NO_PAD.encode(bytes), expiry)
+    }
+
+    /// Decode a stateless, encrypted key back into its target and expiry.
+    ///
+    /// # Panics
+    /// Panics if this `LinkGenerator` was not constructed with [`LinkGenerator::new_encrypted`].
+    pub fn decode_target(&self, key: &str) -> Result<Target, DecodeError> {
+        let encryption_key = self
+            .encryption_key
+            .expect("decode_target requires a LinkGenerator created with `new_encrypted`");
+
+        let bytes = URL_SAFE_NO_PAD
+            .decode(key)
+            .map_err(|_| DecodeError::InvalidEncoding)?;
+
+        if bytes.len() < NONCE_LEN {
+            return Err(DecodeError::Truncated);
+        }
+        let (nonce_bytes, ciphertext) = bytes.split_at(NONCE_LEN);
+
+        let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&encryption_key));
+        let plaintext = cipher
+            .decrypt(Nonce::from_slice(nonce_bytes), ciphertext)
+            .map_err(|_| DecodeError::DecryptionFailed)?;
+
+        let payload: Payload =
+            bincode::deserialize(&plaintext).map_err(|_| DecodeError::Deserialization)?;
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let link = Link {
+            key: String::new(),
+            url: String::new(),
+            expires_at: payload.exp,
+        };
+        if crate::is_expired(&link, now) {
+            return Err(DecodeError::Expired);
+        }
+
+        Ok(Target {
+            target: payload.target,
+            exp: payload.exp,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: [u8; 32] = [7u8; 32];
+
+    #[test]
+    fn round_trips_target() {
+        let gen = LinkGenerator::new_encrypted("/redirect", KEY);
+        let link = gen.encode_target("https://example.com", None);
+
+        let target = gen.decode_target(&link.key).unwrap();
+        assert_eq!(target.target, "https://example.com");
+        assert_eq!(target.exp, None);
+    }
+
+    #[test]
+    fn rejects_expired_target() {
+        let gen = LinkGenerator::new_encrypted("/redirect", KEY);
+        let link = gen.encode_target("https://example.com", Some(0));
+
+        assert!(matches!(
+            gen.decode_target(&link.key),
+            Err(DecodeError::Expired)
+        ));
+    }
+}
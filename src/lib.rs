@@ -8,7 +8,7 @@
 //! fn main() {
 //!     let mut link_gen = LinkGenerator::new("/some/redirect", 10);
 //!     
-//!     let link = link_gen::generate_url()
+//!     let link = link_gen::generate_url(None)
 //!     println!("{:?}",link) // Link { key: "vq5ejng0p6", url: "/some/redirect/vq5ejng0p6" }
 //! }
 //! ```
@@ -18,29 +18,72 @@
 
 use harsh::{Harsh, HarshBuilder};
 use std::num::Wrapping;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 #[cfg(feature = "qrcode")]
 use qrcode::{types::QrError, QrCode};
 
+#[cfg(feature = "encrypted")]
+mod encrypted;
+#[cfg(feature = "encrypted")]
+pub use encrypted::{DecodeError, Target};
+
+mod store;
+pub use store::{InMemoryStore, LinkStore};
+
+#[cfg(feature = "providers")]
+mod providers;
+#[cfg(feature = "providers")]
+pub use providers::{Provider, ProviderClient, ProviderError};
+
+#[cfg(feature = "qrcode")]
+mod qr;
+#[cfg(feature = "qrcode")]
+pub use qr::{ErrorCorrectionLevel, QrFormat, QrImage, QrOptions};
+
 #[derive(Debug, PartialEq, Eq)]
 /// A generated URL and key value
 pub struct Link {
     pub key: String,
     pub url: String,
+    /// The unix timestamp this link expires at, if it was generated with a TTL.
+    pub expires_at: Option<u64>,
 }
 
 impl Link {
-    fn new(base: &str, key: String) -> Self {
+    fn new(base: &str, key: String, expires_at: Option<u64>) -> Self {
         Link {
             url: [base, &key].concat(),
             key,
+            expires_at,
         }
     }
 }
+
+/// Check whether a [`Link`] has expired as of `now` (a unix timestamp).
+pub fn is_expired(link: &Link, now: u64) -> bool {
+    link.expires_at.is_some_and(|exp| exp <= now)
+}
+
+fn expires_at(ttl: Option<Duration>) -> Option<u64> {
+    ttl.map(|ttl| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            + ttl.as_secs()
+    })
+}
 pub struct LinkGenerator {
     id: Wrapping<u64>,
     generator: Harsh,
     pub redirect_url: String,
+    #[cfg(feature = "encrypted")]
+    pub(crate) encryption_key: Option<[u8; 32]>,
+    store: Option<Arc<dyn LinkStore>>,
+    #[cfg(feature = "providers")]
+    provider_client: Option<Arc<ProviderClient>>,
 }
 
 impl LinkGenerator {
@@ -65,9 +108,26 @@ impl LinkGenerator {
                 },
             ]
             .concat(),
+            #[cfg(feature = "encrypted")]
+            encryption_key: None,
+            store: None,
+            #[cfg(feature = "providers")]
+            provider_client: None,
         }
     }
 
+    #[cfg(feature = "encrypted")]
+    /// Create a new LinkGenerator that produces stateless, self-describing keys.
+    ///
+    /// Rather than hashing a sequential internal ID, [`LinkGenerator::encode_target`] and
+    /// [`LinkGenerator::decode_target`] embed the target URL directly into the key, AES-256-GCM
+    /// encrypted with `key`. This lets any node decode a key without shared storage.
+    pub fn new_encrypted(redirect_url: &str, key: [u8; 32]) -> LinkGenerator {
+        let mut gen = Self::new(redirect_url, 10);
+        gen.encryption_key = Some(key);
+        gen
+    }
+
     /// Create a new LinkGenerator with a pre-set internal ID used to generate the hash for the next URL.
     pub fn new_with_internal_id(
         id: u64,
@@ -85,29 +145,87 @@ impl LinkGenerator {
         Self::new_with_salt(redirect_url, length, "")
     }
 
-    /// Generate a new URL.
-    pub fn generate_url(&mut self) -> Link {
-        let hashed = self.generator.encode(&[self.id.0]);
-        self.id += 1;
-        Link::new(&self.redirect_url, hashed)
+    /// Generate a new URL, optionally expiring after `ttl`.
+    ///
+    /// If a [`LinkStore`] has been set via [`LinkGenerator::set_store`], the internal ID is
+    /// sourced from (and persisted to) the store instead of the in-struct counter.
+    pub fn generate_url(&mut self, ttl: Option<Duration>) -> Link {
+        let id = match &self.store {
+            Some(store) => store.next_id(),
+            None => {
+                let id = self.id.0;
+                self.id += 1;
+                id
+            }
+        };
+        let hashed = self.generator.encode(&[id]);
+        Link::new(&self.redirect_url, hashed, expires_at(ttl))
     }
 
     /// Get the current value of the internal ID used to generate the hash for the next URL.
+    ///
+    /// Stale once [`LinkGenerator::set_store`] has been called: IDs are then sourced from the
+    /// store instead, and this keeps reading the now-unused in-struct counter.
     pub fn get_internal_id(&self) -> u64 {
         self.id.0
     }
 
     /// Set the current value of the internal ID used to generate the hash for the next URL.
+    ///
+    /// No-op once [`LinkGenerator::set_store`] has been called: IDs are then sourced from the
+    /// store instead, and this keeps writing the now-unused in-struct counter.
     pub fn set_internal_id(&mut self, input: u64) {
         self.id.0 = input
     }
 
+    /// Source (and persist) the internal ID counter from a durable [`LinkStore`] rather than the
+    /// in-struct counter.
+    ///
+    /// Once set, [`LinkGenerator::get_internal_id`] and [`LinkGenerator::set_internal_id`] no
+    /// longer have any effect on ID generation.
+    pub fn set_store(&mut self, store: Arc<dyn LinkStore>) {
+        self.store = Some(store);
+    }
+
+    /// Recover the internal sequence ID that a key was generated from.
+    pub fn decode_key(&self, key: &str) -> Option<u64> {
+        self.generator.decode(key).ok()?.first().copied()
+    }
+
+    /// Check whether `key` is a well-formed key for this generator (one that decodes to an
+    /// internal ID), without looking it up in a store. This does not check that the ID has
+    /// actually been generated yet.
+    pub fn is_valid_key(&self, key: &str) -> bool {
+        self.decode_key(key).is_some()
+    }
+
     #[cfg(feature = "qrcode")]
-    /// Generate a new URL and QR code.
-    pub fn generate_qr(&mut self) -> Result<(QrCode, Link), QrError> {
-        let link = self.generate_url();
+    /// Generate a new URL and QR code, optionally expiring after `ttl`.
+    pub fn generate_qr(&mut self, ttl: Option<Duration>) -> Result<(QrCode, Link), QrError> {
+        let link = self.generate_url(ttl);
         Ok((QrCode::new(&link.url)?, link))
     }
+
+    #[cfg(feature = "providers")]
+    /// Delegate shortening to one or more third-party providers instead of generating a local
+    /// hashid, using the [`ProviderClient`] set via [`LinkGenerator::set_provider_client`], or a
+    /// default client (3s whole-request timeout) if none has been set.
+    pub async fn generate_via(
+        &self,
+        long_url: &str,
+        providers: &[Provider],
+    ) -> Result<String, ProviderError> {
+        match &self.provider_client {
+            Some(client) => client.generate_via(long_url, providers).await,
+            None => ProviderClient::new().generate_via(long_url, providers).await,
+        }
+    }
+
+    #[cfg(feature = "providers")]
+    /// Set the [`ProviderClient`] used by [`LinkGenerator::generate_via`].
+    pub fn set_provider_client(&mut self, client: Arc<ProviderClient>) {
+        self.provider_client = Some(client);
+    }
 }
 
 #[cfg(test)]
@@ -118,7 +236,7 @@ mod tests {
     #[test]
     fn generate_link() {
         let mut s = LinkGenerator::new("/redirect", 10);
-        let l = s.generate_url();
+        let l = s.generate_url(None);
 
         println!("{:?}", l);
 
@@ -126,7 +244,8 @@ mod tests {
             l,
             Link {
                 key: "vq5ejng0p6".into(),
-                url: "/redirect/vq5ejng0p6".into()
+                url: "/redirect/vq5ejng0p6".into(),
+                expires_at: None,
             }
         );
     }
@@ -134,13 +253,14 @@ mod tests {
     #[test]
     fn generate_link_with_salt() {
         let mut s = LinkGenerator::new_with_salt("/redirect", 10, "salt");
-        let l = s.generate_url();
+        let l = s.generate_url(None);
 
         assert_eq!(
             l,
             Link {
                 key: "9x5eo4n7ow".into(),
-                url: "/redirect/9x5eo4n7ow".into()
+                url: "/redirect/9x5eo4n7ow".into(),
+                expires_at: None,
             }
         );
     }
@@ -148,9 +268,35 @@ mod tests {
     #[test]
     fn generate_qr() -> Result<(), QrError> {
         let mut s = LinkGenerator::new("/redirect", 10);
-        let qr = s.generate_qr();
+        let qr = s.generate_qr(None);
 
         assert!(qr.is_ok());
         Ok(())
     }
+
+    #[test]
+    fn generate_url_with_ttl_sets_expiry() {
+        let mut s = LinkGenerator::new("/redirect", 10);
+        let l = s.generate_url(Some(Duration::from_secs(60)));
+
+        assert!(l.expires_at.is_some());
+        assert!(!is_expired(&l, 0));
+    }
+
+    #[test]
+    fn decode_key_recovers_internal_id() {
+        let mut s = LinkGenerator::new("/redirect", 10);
+        let l = s.generate_url(None);
+
+        assert_eq!(s.decode_key(&l.key), Some(0));
+        assert!(s.is_valid_key(&l.key));
+    }
+
+    #[test]
+    fn decode_key_rejects_garbage() {
+        let s = LinkGenerator::new("/redirect", 10);
+
+        assert_eq!(s.decode_key("not-a-real-key"), None);
+        assert!(!s.is_valid_key("not-a-real-key"));
+    }
 }
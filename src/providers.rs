@@ -0,0 +1,144 @@
+//! Delegating link shortening to third-party providers.
+//!
+//! When callers don't want to run their own redirect endpoint, a [`ProviderClient`] can
+//! transparently shorten a URL via a hosted service instead of generating a local hashid,
+//! trying each configured [`Provider`] in turn until one succeeds.
+
+use std::time::Duration;
+use thiserror::Error;
+
+/// The default request timeout used by a [`ProviderClient`] built with [`ProviderClient::new`].
+///
+/// The timeout bounds the whole request, from connecting until the response body has been read;
+/// it is not a per-read timeout.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// A third-party URL shortening service.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Provider {
+    IsGd,
+    TinyUrl,
+}
+
+impl Provider {
+    fn request_url(&self, long_url: &str) -> String {
+        match self {
+            Provider::IsGd => format!(
+                "https://is.gd/create.php?format=simple&url={}",
+                urlencoding::encode(long_url)
+            ),
+            Provider::TinyUrl => format!(
+                "https://tinyurl.com/api-create.php?url={}",
+                urlencoding::encode(long_url)
+            ),
+        }
+    }
+
+    /// Both `is.gd` and TinyUrl reply with the short URL as a bare response body.
+    fn parse_response(&self, body: &str) -> Option<String> {
+        let trimmed = body.trim();
+        if trimmed.starts_with("http") {
+            Some(trimmed.to_string())
+        } else {
+            None
+        }
+    }
+}
+
+/// Reasons [`ProviderClient::generate_via`] can fail.
+#[derive(Debug, Error)]
+pub enum ProviderError {
+    #[error("no provider in the given list was able to shorten the url")]
+    AllProvidersFailed,
+}
+
+/// A client that delegates link shortening to one or more hosted [`Provider`]s.
+pub struct ProviderClient {
+    client: reqwest::Client,
+}
+
+impl ProviderClient {
+    /// Create a new `ProviderClient` with the default request timeout (3s).
+    pub fn new() -> Self {
+        Self::with_timeout(DEFAULT_TIMEOUT)
+    }
+
+    /// Create a new `ProviderClient` with a custom timeout for the whole request (connecting,
+    /// sending, and reading the response body); it is not a per-read timeout.
+    pub fn with_timeout(timeout: Duration) -> Self {
+        ProviderClient {
+            client: reqwest::Client::builder()
+                .timeout(timeout)
+                .build()
+                .expect("failed to build reqwest client"),
+        }
+    }
+
+    /// Try each provider in order, returning the first successfully shortened URL.
+    pub async fn generate_via(
+        &self,
+        long_url: &str,
+        providers: &[Provider],
+    ) -> Result<String, ProviderError> {
+        for provider in providers {
+            let Ok(response) = self.client.get(provider.request_url(long_url)).send().await
+            else {
+                continue;
+            };
+            let Ok(body) = response.text().await else {
+                continue;
+            };
+            if let Some(short_url) = provider.parse_response(&body) {
+                return Ok(short_url);
+            }
+        }
+        Err(ProviderError::AllProvidersFailed)
+    }
+}
+
+impl Default for ProviderClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_gd_request_url_encodes_target() {
+        assert_eq!(
+            Provider::IsGd.request_url("https://example.com/a?b=c"),
+            "https://is.gd/create.php?format=simple&url=https%3A%2F%2Fexample.com%2Fa%3Fb%3Dc"
+        );
+    }
+
+    #[test]
+    fn tiny_url_request_url_encodes_target() {
+        assert_eq!(
+            Provider::TinyUrl.request_url("https://example.com/a?b=c"),
+            "https://tinyurl.com/api-create.php?url=https%3A%2F%2Fexample.com%2Fa%3Fb%3Dc"
+        );
+    }
+
+    #[test]
+    fn parse_response_accepts_short_url() {
+        assert_eq!(
+            Provider::IsGd.parse_response("https://is.gd/abc123\n"),
+            Some("https://is.gd/abc123".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_response_rejects_error_body() {
+        assert_eq!(
+            Provider::IsGd.parse_response("Error: short URL is not valid"),
+            None
+        );
+        assert_eq!(
+            Provider::TinyUrl.parse_response("<html>rate limited</html>"),
+            None
+        );
+    }
+}
@@ -0,0 +1,252 @@
+//! Configurable QR code rendering for [`LinkGenerator`](crate::LinkGenerator).
+//!
+//! [`LinkGenerator::generate_qr`] hands back a raw [`QrCode`], leaving error-correction, sizing
+//! and rendering entirely to the caller. [`QrOptions`] plus [`LinkGenerator::generate_qr_with`]
+//! turn that thin wrapper into a usable image-generation API, rendering to SVG, Unicode/ANSI
+//! text, or PNG bytes.
+
+use image::Luma;
+use qrcode::render::{svg, unicode};
+use qrcode::types::{EcLevel, QrError};
+use qrcode::QrCode;
+use std::time::Duration;
+
+use crate::{Link, LinkGenerator};
+
+/// QR code error-correction level: higher levels tolerate more damage at the cost of density.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCorrectionLevel {
+    L,
+    M,
+    Q,
+    H,
+}
+
+impl From<ErrorCorrectionLevel> for EcLevel {
+    fn from(level: ErrorCorrectionLevel) -> Self {
+        match level {
+            ErrorCorrectionLevel::L => EcLevel::L,
+            ErrorCorrectionLevel::M => EcLevel::M,
+            ErrorCorrectionLevel::Q => EcLevel::Q,
+            ErrorCorrectionLevel::H => EcLevel::H,
+        }
+    }
+}
+
+/// The image format [`LinkGenerator::generate_qr_with`] should render to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QrFormat {
+    Svg,
+    Text,
+    Png,
+}
+
+/// Rendering options for [`LinkGenerator::generate_qr_with`].
+///
+/// `foreground`/`background` only affect [`QrFormat::Svg`] output: [`QrFormat::Text`] and
+/// [`QrFormat::Png`] render to a monochrome canvas and ignore them.
+#[derive(Debug, Clone)]
+pub struct QrOptions {
+    pub error_correction: ErrorCorrectionLevel,
+    pub module_size: u32,
+    pub quiet_zone: bool,
+    pub foreground: String,
+    pub background: String,
+    pub format: QrFormat,
+}
+
+/// Validate that `color` is a `#rgb` or `#rrggbb` hex string, so it's safe to splice into
+/// rendered SVG markup unescaped.
+fn validate_hex_color(color: &str) -> &str {
+    let valid = color.strip_prefix('#').is_some_and(|hex| {
+        (hex.len() == 3 || hex.len() == 6) && hex.chars().all(|c| c.is_ascii_hexdigit())
+    });
+    assert!(
+        valid,
+        "invalid QrOptions color {color:?}: expected a `#rgb` or `#rrggbb` hex string"
+    );
+    color
+}
+
+impl Default for QrOptions {
+    fn default() -> Self {
+        QrOptions {
+            error_correction: ErrorCorrectionLevel::M,
+            module_size: 8,
+            quiet_zone: true,
+            foreground: "#000000".to_string(),
+            background: "#ffffff".to_string(),
+            format: QrFormat::Svg,
+        }
+    }
+}
+
+impl QrOptions {
+    /// Create a new `QrOptions` with the default rendering settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn error_correction(mut self, level: ErrorCorrectionLevel) -> Self {
+        self.error_correction = level;
+        self
+    }
+
+    pub fn module_size(mut self, size: u32) -> Self {
+        self.module_size = size;
+        self
+    }
+
+    pub fn quiet_zone(mut self, enabled: bool) -> Self {
+        self.quiet_zone = enabled;
+        self
+    }
+
+    /// Set the SVG foreground/background colors.
+    ///
+    /// # Panics
+    /// Panics if either `foreground` or `background` is not a `#rgb`/`#rrggbb` hex string.
+    pub fn colors(mut self, foreground: &str, background: &str) -> Self {
+        self.foreground = validate_hex_color(foreground).to_string();
+        self.background = validate_hex_color(background).to_string();
+        self
+    }
+
+    pub fn format(mut self, format: QrFormat) -> Self {
+        self.format = format;
+        self
+    }
+}
+
+/// A rendered QR code, in the format requested by [`QrOptions::format`].
+pub enum QrImage {
+    Svg(String),
+    Text(String),
+    Png(Vec<u8>),
+}
+
+impl LinkGenerator {
+    /// Generate a new URL, optionally expiring after `ttl`, and render its QR code according to
+    /// `opts`.
+    pub fn generate_qr_with(
+        &mut self,
+        opts: &QrOptions,
+        ttl: Option<Duration>,
+    ) -> Result<(QrImage, Link), QrError> {
+        let link = self.generate_url(ttl);
+        let code = QrCode::with_error_correction_level(&link.url, opts.error_correction.into())?;
+
+        let image = match opts.format {
+            QrFormat::Svg => QrImage::Svg(
+                code.render()
+                    .module_dimensions(opts.module_size, opts.module_size)
+                    .quiet_zone(opts.quiet_zone)
+                    .dark_color(svg::Color(&opts.foreground))
+                    .light_color(svg::Color(&opts.background))
+                    .build(),
+            ),
+            QrFormat::Text => QrImage::Text(
+                code.render::<unicode::Dense1x2>()
+                    .module_dimensions(opts.module_size, opts.module_size)
+                    .quiet_zone(opts.quiet_zone)
+                    .build(),
+            ),
+            QrFormat::Png => {
+                let buffer = code
+                    .render::<Luma<u8>>()
+                    .module_dimensions(opts.module_size, opts.module_size)
+                    .quiet_zone(opts.quiet_zone)
+                    .build();
+                let mut bytes = Vec::new();
+                buffer
+                    .write_to(&mut std::io::Cursor::new(&mut bytes), image::ImageFormat::Png)
+                    .expect("encoding a freshly rendered QR code to PNG cannot fail");
+                QrImage::Png(bytes)
+            }
+        };
+
+        Ok((image, link))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LinkGenerator;
+
+    #[test]
+    fn renders_svg() {
+        let mut s = LinkGenerator::new("/redirect", 10);
+        let opts = QrOptions::new().format(QrFormat::Svg);
+        let (image, _) = s.generate_qr_with(&opts, None).unwrap();
+
+        match image {
+            QrImage::Svg(svg) => assert!(!svg.is_empty()),
+            _ => panic!("expected QrImage::Svg"),
+        }
+    }
+
+    #[test]
+    fn renders_text() {
+        let mut s = LinkGenerator::new("/redirect", 10);
+        let opts = QrOptions::new().format(QrFormat::Text);
+        let (image, _) = s.generate_qr_with(&opts, None).unwrap();
+
+        match image {
+            QrImage::Text(text) => assert!(!text.is_empty()),
+            _ => panic!("expected QrImage::Text"),
+        }
+    }
+
+    #[test]
+    fn renders_png() {
+        let mut s = LinkGenerator::new("/redirect", 10);
+        let opts = QrOptions::new().format(QrFormat::Png);
+        let (image, _) = s.generate_qr_with(&opts, None).unwrap();
+
+        match image {
+            QrImage::Png(bytes) => assert!(!bytes.is_empty()),
+            _ => panic!("expected QrImage::Png"),
+        }
+    }
+
+    #[test]
+    fn module_size_affects_rendered_png_dimensions() {
+        use image::GenericImageView;
+
+        let mut s = LinkGenerator::new("/redirect", 10);
+        let small = QrOptions::new().format(QrFormat::Png).module_size(2);
+        let large = QrOptions::new().format(QrFormat::Png).module_size(10);
+
+        let (QrImage::Png(small_bytes), _) = s.generate_qr_with(&small, None).unwrap() else {
+            panic!("expected QrImage::Png");
+        };
+        let (QrImage::Png(large_bytes), _) = s.generate_qr_with(&large, None).unwrap() else {
+            panic!("expected QrImage::Png");
+        };
+
+        let small_width = image::load_from_memory(&small_bytes).unwrap().dimensions().0;
+        let large_width = image::load_from_memory(&large_bytes).unwrap().dimensions().0;
+
+        assert!(large_width > small_width);
+    }
+
+    #[test]
+    fn error_correction_level_maps_to_ec_level() {
+        assert_eq!(EcLevel::from(ErrorCorrectionLevel::L), EcLevel::L);
+        assert_eq!(EcLevel::from(ErrorCorrectionLevel::H), EcLevel::H);
+    }
+
+    #[test]
+    fn colors_accepts_valid_hex() {
+        let opts = QrOptions::new().colors("#abc", "#aabbcc");
+        assert_eq!(opts.foreground, "#abc");
+        assert_eq!(opts.background, "#aabbcc");
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid QrOptions color")]
+    fn colors_rejects_non_hex_input() {
+        QrOptions::new().colors("<script>alert(1)</script>", "#ffffff");
+    }
+}
@@ -0,0 +1,72 @@
+//! A pluggable storage backend for [`LinkGenerator`](crate::LinkGenerator).
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// A backing store for generated links.
+pub trait LinkStore: Send + Sync {
+    /// Persist a mapping from `key` to `target`, along with its optional expiry.
+    fn insert(&self, key: &str, target: &str, expires_at: Option<u64>);
+
+    /// Look up the target and expiry a `key` was created for.
+    fn get(&self, key: &str) -> Option<(String, Option<u64>)>;
+
+    /// Return the next value to use for the generator's internal ID counter.
+    fn next_id(&self) -> u64;
+}
+
+/// An in-memory [`LinkStore`], useful for tests and single-process deployments.
+#[derive(Default)]
+pub struct InMemoryStore {
+    data: Mutex<HashMap<String, (String, Option<u64>)>>,
+    counter: AtomicU64,
+}
+
+impl InMemoryStore {
+    /// Create a new, empty `InMemoryStore`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl LinkStore for InMemoryStore {
+    fn insert(&self, key: &str, target: &str, expires_at: Option<u64>) {
+        self.data
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), (target.to_string(), expires_at));
+    }
+
+    fn get(&self, key: &str) -> Option<(String, Option<u64>)> {
+        self.data.lock().unwrap().get(key).cloned()
+    }
+
+    fn next_id(&self) -> u64 {
+        self.counter.fetch_add(1, Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn in_memory_store_round_trips() {
+        let store = InMemoryStore::new();
+        store.insert("abc", "https://example.com", Some(123));
+        assert_eq!(
+            store.get("abc"),
+            Some(("https://example.com".to_string(), Some(123)))
+        );
+        assert_eq!(store.get("missing"), None);
+    }
+
+    #[test]
+    fn in_memory_store_next_id_increments() {
+        let store = InMemoryStore::new();
+        assert_eq!(store.next_id(), 0);
+        assert_eq!(store.next_id(), 1);
+        assert_eq!(store.next_id(), 2);
+    }
+}